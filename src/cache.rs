@@ -0,0 +1,230 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use serde_json::Value;
+
+/// How long a cached response for a given method stays valid.
+#[derive(Clone, Copy, Debug)]
+pub enum Ttl {
+    /// Never evicted: only dropped under cache capacity pressure.
+    Forever,
+    /// Evicted once the given duration has elapsed since insertion.
+    After(Duration),
+    /// Never cached; every call goes to the upstream.
+    Never,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct CacheKey(String);
+
+impl CacheKey {
+    fn new(method: &str, params: &Value) -> Self {
+        Self(format!("{method}:{params}"))
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    inserted_at: Instant,
+    ttl: Ttl,
+}
+
+impl Entry {
+    fn is_expired(&self) -> bool {
+        match self.ttl {
+            Ttl::Forever => false,
+            Ttl::Never => true,
+            Ttl::After(duration) => self.inserted_at.elapsed() > duration,
+        }
+    }
+}
+
+/// Per-method TTL policy, plus the default applied to methods with no
+/// explicit entry (no caching, by default).
+#[derive(Clone, Debug)]
+pub struct CachePolicy {
+    pub default_ttl: Ttl,
+    pub overrides: HashMap<&'static str, Ttl>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        let mut overrides = HashMap::new();
+        overrides.insert("zks_getBytecodeByHash", Ttl::Forever);
+        overrides.insert("zks_getMainContract", Ttl::Forever);
+        overrides.insert("zks_getBridgeContracts", Ttl::Forever);
+        // Block/batch/raw-tx details for an unproven block can still be
+        // reorganized on L1, so they aren't safe to cache on a flat TTL —
+        // callers gate insertion on proven status themselves (see
+        // `Server::forward_if` call sites in `rpc/zks.rs`) and rely on
+        // `Forever` here once that gate has already passed.
+        overrides.insert("zks_getBlockDetails", Ttl::Forever);
+        overrides.insert("zks_getL1BatchDetails", Ttl::Forever);
+        overrides.insert("zks_getRawBlockTransactions", Ttl::Forever);
+        overrides.insert("zks_getL1GasPrice", Ttl::After(Duration::from_secs(5)));
+        overrides.insert("zks_getFeeParams", Ttl::After(Duration::from_secs(5)));
+
+        Self {
+            default_ttl: Ttl::Never,
+            overrides,
+        }
+    }
+}
+
+impl CachePolicy {
+    fn ttl_for(&self, method: &str) -> Ttl {
+        self.overrides
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+/// Hit/miss counters exposed for metrics scraping.
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheMetrics {
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+/// LRU cache of upstream responses keyed by method + params, with a
+/// per-method TTL policy so immutable data (bytecode, finalized batch
+/// details) can be cached indefinitely while volatile data (gas price,
+/// fee params) gets a short TTL.
+#[derive(Debug)]
+pub struct ResponseCache {
+    entries: Mutex<LruCache<CacheKey, Entry>>,
+    policy: CachePolicy,
+    metrics: CacheMetrics,
+}
+
+impl ResponseCache {
+    pub fn new(capacity: NonZeroUsize, policy: CachePolicy) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            policy,
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> &CacheMetrics {
+        &self.metrics
+    }
+
+    pub(crate) fn get(&self, method: &str, params: &Value) -> Option<Value> {
+        let key = CacheKey::new(method, params);
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(&key) {
+            Some(entry) if !entry.is_expired() => {
+                self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.value.clone())
+            }
+            Some(_) => {
+                entries.pop(&key);
+                self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert(&self, method: &str, params: &Value, value: Value) {
+        let ttl = self.policy.ttl_for(method);
+        if matches!(ttl, Ttl::Never) {
+            return;
+        }
+
+        let key = CacheKey::new(method, params);
+        self.entries.lock().unwrap().put(
+            key,
+            Entry {
+                value,
+                inserted_at: Instant::now(),
+                ttl,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_with(method: &'static str, ttl: Ttl) -> ResponseCache {
+        let mut policy = CachePolicy {
+            default_ttl: Ttl::Never,
+            overrides: HashMap::new(),
+        };
+        policy.overrides.insert(method, ttl);
+        ResponseCache::new(NonZeroUsize::new(8).unwrap(), policy)
+    }
+
+    #[test]
+    fn hit_returns_the_cached_value() {
+        let cache = cache_with("zks_getL1GasPrice", Ttl::Forever);
+        cache.insert("zks_getL1GasPrice", &Value::Null, Value::from(42));
+
+        assert_eq!(cache.get("zks_getL1GasPrice", &Value::Null), Some(Value::from(42)));
+        assert_eq!(cache.metrics().hits(), 1);
+    }
+
+    #[test]
+    fn miss_on_unknown_key_is_counted() {
+        let cache = cache_with("zks_getL1GasPrice", Ttl::Forever);
+        assert_eq!(cache.get("zks_getL1GasPrice", &Value::Null), None);
+        assert_eq!(cache.metrics().misses(), 1);
+    }
+
+    #[test]
+    fn never_ttl_is_not_stored() {
+        let cache = cache_with("zks_getL1GasPrice", Ttl::Never);
+        cache.insert("zks_getL1GasPrice", &Value::Null, Value::from(42));
+
+        assert_eq!(cache.get("zks_getL1GasPrice", &Value::Null), None);
+    }
+
+    #[test]
+    fn after_ttl_expires_the_entry_is_dropped() {
+        let cache = cache_with("zks_getL1GasPrice", Ttl::After(Duration::from_millis(0)));
+        cache.insert("zks_getL1GasPrice", &Value::Null, Value::from(42));
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(cache.get("zks_getL1GasPrice", &Value::Null), None);
+    }
+
+    #[test]
+    fn methods_with_no_override_use_the_default_ttl() {
+        let cache = ResponseCache::new(
+            NonZeroUsize::new(8).unwrap(),
+            CachePolicy {
+                default_ttl: Ttl::Never,
+                overrides: HashMap::new(),
+            },
+        );
+        cache.insert("some_unlisted_method", &Value::Null, Value::from(1));
+
+        assert_eq!(cache.get("some_unlisted_method", &Value::Null), None);
+    }
+}
@@ -0,0 +1,119 @@
+//! Fee suggestion and escalation helpers layered on top of the plain
+//! `zks_getL1GasPrice`/`zks_getFeeParams` forwards.
+
+use serde::{Deserialize, Serialize};
+use zksync_types::U256;
+
+/// A recommended fee pair for submitting a new transaction, derived from
+/// recent block history.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    /// Average `gasUsed / gasLimit` across the sampled blocks, mirroring the
+    /// ratio `eth_feeHistory` reports alongside its base-fee percentiles.
+    pub gas_used_ratio: f64,
+}
+
+/// Returns the value at `percentile` (0-100) of an already-sorted slice.
+pub(crate) fn percentile(sorted: &[U256], percentile: u64) -> Option<U256> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let rank = (sorted.len() as u64 - 1) * percentile.min(100) / 100;
+    sorted.get(rank as usize).copied()
+}
+
+/// Maps `(original_fee, attempt_number)` to a bumped fee, driving
+/// transaction resubmission when a tx is stuck. Attempt numbers start at 1
+/// for the first resubmission.
+pub struct EscalationPolicy(Box<dyn Fn(U256, usize) -> U256 + Send + Sync>);
+
+impl EscalationPolicy {
+    pub fn new(policy: impl Fn(U256, usize) -> U256 + Send + Sync + 'static) -> Self {
+        Self(Box::new(policy))
+    }
+
+    pub fn escalate(&self, original_fee: U256, attempt: usize) -> U256 {
+        (self.0)(original_fee, attempt)
+    }
+
+    /// Bumps the fee by `bump_percent` of the original fee per attempt.
+    pub fn linear(bump_percent: u64) -> Self {
+        Self::new(move |fee, attempt| {
+            fee + fee * U256::from(bump_percent) * U256::from(attempt) / U256::from(100)
+        })
+    }
+
+    /// Compounds a `bump_percent` increase per attempt.
+    pub fn geometric(bump_percent: u64) -> Self {
+        Self::new(move |fee, attempt| {
+            (0..attempt).fold(fee, |fee, _| fee + fee * U256::from(bump_percent) / U256::from(100))
+        })
+    }
+}
+
+impl Default for EscalationPolicy {
+    /// +10% of the original fee per attempt.
+    fn default() -> Self {
+        Self::linear(10)
+    }
+}
+
+impl std::fmt::Debug for EscalationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EscalationPolicy").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_picks_the_requested_rank() {
+        let sorted: Vec<U256> = (0..=10).map(U256::from).collect();
+
+        assert_eq!(percentile(&sorted, 0), Some(U256::from(0)));
+        assert_eq!(percentile(&sorted, 50), Some(U256::from(5)));
+        assert_eq!(percentile(&sorted, 100), Some(U256::from(10)));
+    }
+
+    #[test]
+    fn percentile_clamps_above_100() {
+        let sorted: Vec<U256> = (0..=10).map(U256::from).collect();
+        assert_eq!(percentile(&sorted, 250), percentile(&sorted, 100));
+    }
+
+    #[test]
+    fn percentile_of_empty_slice_is_none() {
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn linear_escalation_scales_with_attempt_number() {
+        let policy = EscalationPolicy::linear(10);
+        let fee = U256::from(1000);
+
+        assert_eq!(policy.escalate(fee, 1), U256::from(1100));
+        assert_eq!(policy.escalate(fee, 2), U256::from(1200));
+    }
+
+    #[test]
+    fn geometric_escalation_compounds() {
+        let policy = EscalationPolicy::geometric(10);
+        let fee = U256::from(1000);
+
+        assert_eq!(policy.escalate(fee, 1), U256::from(1100));
+        // 1100 + 10% of 1100 = 1210.
+        assert_eq!(policy.escalate(fee, 2), U256::from(1210));
+    }
+
+    #[test]
+    fn default_escalation_is_linear_ten_percent() {
+        let policy = EscalationPolicy::default();
+        assert_eq!(policy.escalate(U256::from(1000), 1), U256::from(1100));
+    }
+}
@@ -1,3 +1,8 @@
+pub mod cache;
+pub mod fees;
+pub mod middleware;
+pub mod pool;
+pub mod proof;
 pub mod rpc;
 
 #[derive(Debug, thiserror::Error)]
@@ -5,6 +10,21 @@ pub enum ClaraError {
     #[error(transparent)]
     ClientError(#[from] jsonrpsee::core::ClientError),
 
+    #[error("rate limit exceeded for method `{method}`")]
+    RateLimited { method: String },
+
+    #[error("method `{method}` is not allowed on this endpoint")]
+    MethodNotAllowed { method: String },
+
+    #[error("storage proof for {address:?} key {key:?} failed local verification")]
+    ProofVerificationFailed {
+        address: zksync_types::Address,
+        key: zksync_types::H256,
+    },
+
+    #[error("l1 batch {batch} has not been sealed with a root hash yet")]
+    BatchRootUnavailable { batch: zksync_types::L1BatchNumber },
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -1,11 +1,11 @@
 use jsonrpsee::http_client::HttpClientBuilder;
 
-use clara::rpc::Server;
+use clara::{pool::SelectionPolicy, rpc::Server};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = HttpClientBuilder::default().build("https://mainnet.era.zksync.io")?;
-    let server = Server::new(client);
+    let server = Server::new(vec![client], SelectionPolicy::RoundRobin);
 
     match server.run().await {
         Ok((addr, handle)) => {
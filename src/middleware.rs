@@ -0,0 +1,247 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use jsonrpsee::core::async_trait;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::ClaraError;
+
+/// Cross-cutting hook that every proxied call passes through on its way to
+/// and from the upstream zkSync node. `before_call` runs, in registration
+/// order, ahead of the upstream request; the first one to return an `Err`
+/// aborts the call before the upstream is ever touched, and that error is
+/// returned to the caller instead. `after_call` then runs, in the same
+/// order, once the (possibly cached) result is known, for observability
+/// only — it cannot veto a result that already happened.
+///
+/// `peer` is the caller's remote address, when the transport and
+/// `jsonrpsee`'s connection `Extensions` exposed one for this call (see
+/// `rpc::peer_addr`); `None` for transports/tests that don't carry one.
+#[async_trait]
+pub trait Middleware: std::fmt::Debug + Send + Sync {
+    async fn before_call(&self, method: &str, params: &Value, peer: Option<SocketAddr>) -> Result<(), ClaraError>;
+
+    async fn after_call(
+        &self,
+        _method: &str,
+        _params: &Value,
+        _peer: Option<SocketAddr>,
+        _result: &Result<Value, String>,
+    ) {
+    }
+}
+
+/// Logs every proxied call's request and response (or error) at the `info`
+/// level.
+#[derive(Debug, Default)]
+pub struct LoggingMiddleware;
+
+#[async_trait]
+impl Middleware for LoggingMiddleware {
+    async fn before_call(&self, method: &str, params: &Value, peer: Option<SocketAddr>) -> Result<(), ClaraError> {
+        info!(method, %params, ?peer, "forwarding call to upstream");
+        Ok(())
+    }
+
+    async fn after_call(
+        &self,
+        method: &str,
+        _params: &Value,
+        peer: Option<SocketAddr>,
+        result: &Result<Value, String>,
+    ) {
+        match result {
+            Ok(response) => info!(method, %response, ?peer, "upstream call completed"),
+            Err(error) => warn!(method, error, ?peer, "upstream call failed"),
+        }
+    }
+}
+
+/// Rejects a call once more than `limit` requests from the same caller have
+/// been observed within the current `window`. Windows are fixed, not
+/// sliding: a bucket's window starts at its first request and restarts with
+/// the first request made after `window` has elapsed.
+///
+/// Keyed by `(peer, method)` when the caller's address is available, so one
+/// abusive client can't exhaust the budget for everyone else; calls whose
+/// transport didn't expose a peer address (see [`Middleware`]'s docs) all
+/// share a single fallback bucket per method, so they're still limited in
+/// aggregate rather than unlimited.
+#[derive(Debug)]
+pub struct RateLimitMiddleware {
+    limit: u32,
+    window: Duration,
+    hits: Mutex<HashMap<(Option<SocketAddr>, String), (Instant, u32)>>,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn before_call(&self, method: &str, _params: &Value, peer: Option<SocketAddr>) -> Result<(), ClaraError> {
+        let mut hits = self.hits.lock().unwrap();
+        let now = Instant::now();
+        let entry = hits.entry((peer, method.to_string())).or_insert((now, 0));
+
+        if now.duration_since(entry.0) > self.window {
+            *entry = (now, 0);
+        }
+        entry.1 += 1;
+
+        if entry.1 > self.limit {
+            warn!(method, ?peer, limit = self.limit, "rate limit exceeded");
+            return Err(ClaraError::RateLimited {
+                method: method.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Lets operators expose only an allow-listed set of methods, or block a
+/// deny-listed set, without editing every forwarding method by hand.
+#[derive(Debug, Default)]
+pub struct MethodFilterMiddleware {
+    allow: Option<HashSet<String>>,
+    deny: HashSet<String>,
+}
+
+impl MethodFilterMiddleware {
+    pub fn allow_only(methods: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            allow: Some(methods.into_iter().collect()),
+            deny: HashSet::new(),
+        }
+    }
+
+    pub fn deny(methods: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            allow: None,
+            deny: methods.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for MethodFilterMiddleware {
+    async fn before_call(&self, method: &str, _params: &Value, _peer: Option<SocketAddr>) -> Result<(), ClaraError> {
+        if self.deny.contains(method) {
+            return Err(ClaraError::MethodNotAllowed {
+                method: method.to_string(),
+            });
+        }
+
+        if let Some(allow) = &self.allow {
+            if !allow.contains(method) {
+                return Err(ClaraError::MethodNotAllowed {
+                    method: method.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(port: u16) -> Option<SocketAddr> {
+        Some(SocketAddr::from(([127, 0, 0, 1], port)))
+    }
+
+    #[tokio::test]
+    async fn rate_limit_rejects_after_threshold_within_window() {
+        let middleware = RateLimitMiddleware::new(2, Duration::from_secs(60));
+
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(1))
+            .await
+            .is_ok());
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(1))
+            .await
+            .is_ok());
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(1))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn rate_limit_is_scoped_per_method() {
+        let middleware = RateLimitMiddleware::new(1, Duration::from_secs(60));
+
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(1))
+            .await
+            .is_ok());
+        assert!(middleware
+            .before_call("zks_getFeeParams", &Value::Null, peer(1))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn rate_limit_is_scoped_per_peer() {
+        let middleware = RateLimitMiddleware::new(1, Duration::from_secs(60));
+
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(1))
+            .await
+            .is_ok());
+        // A different caller hitting the same method still gets its own budget.
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(2))
+            .await
+            .is_ok());
+        // The first caller is still over its own limit, though.
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, peer(1))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn method_filter_allow_only_rejects_unlisted_methods() {
+        let middleware = MethodFilterMiddleware::allow_only(["zks_getL1GasPrice".to_string()]);
+
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, None)
+            .await
+            .is_ok());
+        assert!(middleware
+            .before_call("zks_getFeeParams", &Value::Null, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn method_filter_deny_rejects_listed_methods() {
+        let middleware = MethodFilterMiddleware::deny(["zks_getFeeParams".to_string()]);
+
+        assert!(middleware
+            .before_call("zks_getL1GasPrice", &Value::Null, None)
+            .await
+            .is_ok());
+        assert!(middleware
+            .before_call("zks_getFeeParams", &Value::Null, None)
+            .await
+            .is_err());
+    }
+}
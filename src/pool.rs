@@ -0,0 +1,347 @@
+//! A pool of upstream zkSync JSON-RPC endpoints behind a single `ClientT`,
+//! so a single flaky or down node doesn't take the whole proxy down with
+//! it. Implementing `ClientT` directly (rather than, say, exposing
+//! `fn client(&self) -> &HttpClient`) means `Server` keeps calling
+//! `self.zksync.<method>()` everywhere unchanged; the pool decides which
+//! endpoint actually serves each call.
+
+use std::{
+    sync::{
+        atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use jsonrpsee::{
+    core::{
+        client::{BatchResponse, ClientT},
+        params::BatchRequestBuilder,
+        traits::ToRpcParams,
+        ClientError,
+    },
+    http_client::HttpClient,
+};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+/// How the pool picks which endpoint to try first for a given call.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum SelectionPolicy {
+    /// Cycle through endpoints on every call.
+    #[default]
+    RoundRobin,
+    /// Always prefer endpoint 0, falling back to the rest in order.
+    PrimaryWithFallback,
+}
+
+const EJECT_AFTER_FAILURES: u32 = 3;
+const EJECT_DURATION: Duration = Duration::from_secs(30);
+
+struct Endpoint {
+    client: HttpClient,
+    consecutive_failures: AtomicU32,
+    ejected_until: Mutex<Option<Instant>>,
+    total_latency_ms: AtomicU64,
+    total_requests: AtomicU64,
+}
+
+impl Endpoint {
+    fn new(client: HttpClient) -> Self {
+        Self {
+            client,
+            consecutive_failures: AtomicU32::new(0),
+            ejected_until: Mutex::new(None),
+            total_latency_ms: AtomicU64::new(0),
+            total_requests: AtomicU64::new(0),
+        }
+    }
+
+    fn is_ejected(&self) -> bool {
+        match *self.ejected_until.lock().unwrap() {
+            Some(until) => Instant::now() < until,
+            None => false,
+        }
+    }
+
+    fn record_success(&self, elapsed: Duration) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.ejected_until.lock().unwrap() = None;
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        self.total_latency_ms
+            .fetch_add(elapsed.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= EJECT_AFTER_FAILURES {
+            *self.ejected_until.lock().unwrap() = Some(Instant::now() + EJECT_DURATION);
+        }
+    }
+
+    /// Average observed latency in milliseconds, if any requests have succeeded.
+    fn average_latency_ms(&self) -> Option<u64> {
+        let requests = self.total_requests.load(Ordering::Relaxed);
+        if requests == 0 {
+            return None;
+        }
+        Some(self.total_latency_ms.load(Ordering::Relaxed) / requests)
+    }
+}
+
+struct PoolInner {
+    endpoints: Vec<Endpoint>,
+    policy: SelectionPolicy,
+    next: AtomicUsize,
+}
+
+/// A `ClientT` backed by multiple upstream zkSync nodes, retrying the next
+/// endpoint on a transport-level `ClientError` (see [`is_transport_failure`])
+/// before giving up; any other error is returned as-is without a retry. A flapping endpoint is
+/// temporarily ejected after a few consecutive failures and re-probed once
+/// its cool-down expires.
+#[derive(Clone)]
+pub struct UpstreamPool {
+    inner: Arc<PoolInner>,
+}
+
+impl std::fmt::Debug for UpstreamPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpstreamPool")
+            .field("endpoints", &self.inner.endpoints.len())
+            .field("policy", &self.inner.policy)
+            .finish()
+    }
+}
+
+impl UpstreamPool {
+    pub fn new(clients: Vec<HttpClient>, policy: SelectionPolicy) -> Self {
+        assert!(!clients.is_empty(), "upstream pool needs at least one endpoint");
+
+        Self {
+            inner: Arc::new(PoolInner {
+                endpoints: clients.into_iter().map(Endpoint::new).collect(),
+                policy,
+                next: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Average latency per configured endpoint, in request order.
+    pub fn endpoint_latencies_ms(&self) -> Vec<Option<u64>> {
+        self.inner
+            .endpoints
+            .iter()
+            .map(Endpoint::average_latency_ms)
+            .collect()
+    }
+
+    /// Endpoints to try, in order, for the next call: healthy ones first in
+    /// the policy's preferred order, with ejected ones only brought back in
+    /// if every endpoint is currently ejected (better a stale node than no
+    /// proxy at all).
+    fn candidates(&self) -> Vec<&Endpoint> {
+        let start = match self.inner.policy {
+            SelectionPolicy::RoundRobin => self.inner.next.fetch_add(1, Ordering::Relaxed),
+            SelectionPolicy::PrimaryWithFallback => 0,
+        };
+
+        let len = self.inner.endpoints.len();
+        let ordered: Vec<&Endpoint> = (0..len)
+            .map(|offset| &self.inner.endpoints[(start + offset) % len])
+            .collect();
+
+        let healthy: Vec<&Endpoint> = ordered.iter().copied().filter(|e| !e.is_ejected()).collect();
+        if healthy.is_empty() {
+            ordered
+        } else {
+            healthy
+        }
+    }
+
+    fn clone_params(params: &Option<Box<RawValue>>) -> Result<Option<Box<RawValue>>, ClientError> {
+        params
+            .as_deref()
+            .map(|raw| RawValue::from_string(raw.get().to_owned()))
+            .transpose()
+            .map_err(|e| ClientError::Custom(e.to_string()))
+    }
+}
+
+/// Whether `err` reflects the *endpoint* being unreachable/unhealthy, as
+/// opposed to an ordinary JSON-RPC application error (a reverted `eth_call`,
+/// invalid params, "method not found") that every other endpoint would
+/// reject identically. Only the former should count against an endpoint's
+/// health or justify retrying a different one.
+fn is_transport_failure(err: &ClientError) -> bool {
+    matches!(
+        err,
+        ClientError::Transport(_) | ClientError::RequestTimeout | ClientError::RestartNeeded(_)
+    )
+}
+
+#[jsonrpsee::core::async_trait]
+impl ClientT for UpstreamPool {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), ClientError>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = params
+            .to_rpc_params()
+            .map_err(|e| ClientError::Custom(e.to_string()))?;
+
+        let mut last_err = None;
+        for endpoint in self.candidates() {
+            let retry_params = Self::clone_params(&params)?;
+            match endpoint.client.notification(method, retry_params).await {
+                Ok(()) => return Ok(()),
+                Err(err) if is_transport_failure(&err) => {
+                    endpoint.record_failure();
+                    last_err = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| ClientError::Custom("no upstream endpoints configured".into())))
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, ClientError>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = params
+            .to_rpc_params()
+            .map_err(|e| ClientError::Custom(e.to_string()))?;
+
+        let mut last_err = None;
+        for endpoint in self.candidates() {
+            let retry_params = Self::clone_params(&params)?;
+            let started = Instant::now();
+            match endpoint.client.request(method, retry_params).await {
+                Ok(value) => {
+                    endpoint.record_success(started.elapsed());
+                    return Ok(value);
+                }
+                Err(err) if is_transport_failure(&err) => {
+                    endpoint.record_failure();
+                    last_err = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| ClientError::Custom("no upstream endpoints configured".into())))
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, ClientError>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        // Batches aren't split across endpoints: send the whole batch to the
+        // first healthy endpoint, falling back to the rest on failure.
+        let mut last_err = None;
+        for endpoint in self.candidates() {
+            let started = Instant::now();
+            match endpoint.client.batch_request(batch.clone()).await {
+                Ok(response) => {
+                    endpoint.record_success(started.elapsed());
+                    return Ok(response);
+                }
+                Err(err) if is_transport_failure(&err) => {
+                    endpoint.record_failure();
+                    last_err = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| ClientError::Custom("no upstream endpoints configured".into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::http_client::HttpClientBuilder;
+
+    use super::*;
+
+    fn dummy_client(port: u16) -> HttpClient {
+        HttpClientBuilder::default()
+            .build(format!("http://127.0.0.1:{port}"))
+            .unwrap()
+    }
+
+    fn pool(endpoints: usize, policy: SelectionPolicy) -> UpstreamPool {
+        UpstreamPool::new((0..endpoints).map(|i| dummy_client(9000 + i as u16)).collect(), policy)
+    }
+
+    #[test]
+    fn is_transport_failure_only_matches_transport_level_errors() {
+        assert!(is_transport_failure(&ClientError::RequestTimeout));
+        assert!(!is_transport_failure(&ClientError::Custom("method not found".into())));
+    }
+
+    #[test]
+    fn round_robin_advances_the_starting_endpoint_each_call() {
+        let pool = pool(3, SelectionPolicy::RoundRobin);
+
+        let before = pool.inner.next.load(Ordering::Relaxed);
+        let candidates = pool.candidates();
+        let after = pool.inner.next.load(Ordering::Relaxed);
+
+        assert_eq!(after, before + 1);
+        assert_eq!(candidates.len(), 3);
+    }
+
+    #[test]
+    fn primary_with_fallback_always_starts_at_endpoint_zero() {
+        let pool = pool(3, SelectionPolicy::PrimaryWithFallback);
+        let first_call = pool.candidates();
+        let second_call = pool.candidates();
+        assert!(std::ptr::eq(first_call[0], &pool.inner.endpoints[0]));
+        assert!(std::ptr::eq(second_call[0], &pool.inner.endpoints[0]));
+        assert_eq!(first_call.len(), second_call.len());
+        assert_eq!(first_call.len(), 3);
+    }
+
+    #[test]
+    fn an_endpoint_is_ejected_after_enough_consecutive_failures() {
+        let endpoint = Endpoint::new(dummy_client(9100));
+        assert!(!endpoint.is_ejected());
+
+        for _ in 0..EJECT_AFTER_FAILURES {
+            endpoint.record_failure();
+        }
+        assert!(endpoint.is_ejected());
+    }
+
+    #[test]
+    fn a_success_resets_the_failure_count_and_clears_ejection() {
+        let endpoint = Endpoint::new(dummy_client(9101));
+        for _ in 0..EJECT_AFTER_FAILURES {
+            endpoint.record_failure();
+        }
+        assert!(endpoint.is_ejected());
+
+        endpoint.record_success(Duration::from_millis(5));
+        assert!(!endpoint.is_ejected());
+        assert_eq!(endpoint.average_latency_ms(), Some(5));
+    }
+
+    #[test]
+    fn candidates_falls_back_to_every_endpoint_when_all_are_ejected() {
+        let pool = pool(2, SelectionPolicy::PrimaryWithFallback);
+        for endpoint in &pool.inner.endpoints {
+            for _ in 0..EJECT_AFTER_FAILURES {
+                endpoint.record_failure();
+            }
+        }
+
+        assert_eq!(pool.candidates().len(), 2);
+    }
+}
@@ -0,0 +1,215 @@
+//! Local verification of the storage proofs returned by `zks_getProof`,
+//! so Clara doesn't have to blindly trust whatever the upstream node hands
+//! back (the same trust-minimization idea as a light client verifying
+//! against a known state root).
+//!
+//! zkSync Era's state tree is a single flat sparse Merkle tree hashed with
+//! Blake2s-256, *not* a two-tier account/storage trie like Ethereum's: every
+//! leaf is keyed by `hash(address, key)` rather than by `key` alone, so one
+//! tree simultaneously commits to every account's storage. There is no
+//! separate "account proof" to verify here — folding `address` into the
+//! leaf's tree key (see [`tree_key`]) is what makes a `zks_getProof` proof
+//! address-specific, and verifying the recomputed root below is therefore
+//! already verifying the account, not just the slot.
+
+use blake2::{Blake2s256, Digest};
+use zksync_types::{api::StorageProof, Address, H256};
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+    let mut hasher = Blake2s256::new();
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    H256::from_slice(&hasher.finalize())
+}
+
+/// Folds `address` and the raw storage `key` into the single tree key
+/// zkSync Era's state tree actually indexes leaves by. `address` is hashed
+/// as its raw 20 bytes, unpadded — there's no ambiguity to pad against since
+/// both inputs are fixed-width, but this (along with `leaf_hash` folding
+/// `tree_key` in with `value`/`index`) is still only checked for internal
+/// consistency in this crate's tests, not against a real
+/// `zksync_merkle_tree` preimage; see the `proof` module tests for the gap.
+fn tree_key(address: Address, key: H256) -> H256 {
+    let mut hasher = Blake2s256::new();
+    hasher.update(address.as_bytes());
+    hasher.update(key.as_bytes());
+    H256::from_slice(&hasher.finalize())
+}
+
+/// Each leaf is keyed by an ever-increasing `index` (assigned the first time
+/// a slot is written), not just `tree_key`/`value` — two different slots
+/// that happened to share a key/value pair at different points in the
+/// tree's history must still hash to distinct leaves.
+fn leaf_hash(tree_key: H256, value: H256, index: u64) -> H256 {
+    let mut hasher = Blake2s256::new();
+    hasher.update(tree_key.as_bytes());
+    hasher.update(value.as_bytes());
+    hasher.update(index.to_be_bytes());
+    H256::from_slice(&hasher.finalize())
+}
+
+/// Whether bit `index` of `key` is set, counting from the *least*
+/// significant bit (`index == 0`) upward. The tree has a fixed depth of 256
+/// and the root branches on the key's most significant bit, so walking the
+/// proof leaf-to-root (as [`verify_storage_proof`] does, `i` starting at the
+/// leaf) consults the least significant bit first — counting this way keeps
+/// that loop a plain `i` instead of flipping the index at every level.
+fn bit_at(key: &H256, index: usize) -> bool {
+    let byte = key.as_bytes()[31 - index / 8];
+    (byte >> (index % 8)) & 1 == 1
+}
+
+/// Recomputes the leaf for `proof` (scoped to `address`) and walks its
+/// sibling path up to the root, returning whether the result matches
+/// `expected_root`. `proof.proof` holds one sibling hash per tree level,
+/// ordered leaf-to-root, and the path bit consulted at level `i` is bit `i`
+/// of the tree key counting from the least significant bit (see [`bit_at`]),
+/// since the root itself branches on the most significant bit.
+pub fn verify_storage_proof(address: Address, proof: &StorageProof, expected_root: H256) -> bool {
+    let key = tree_key(address, proof.key);
+    let mut node = leaf_hash(key, proof.value, proof.index);
+
+    for (i, sibling) in proof.proof.iter().enumerate() {
+        node = if bit_at(&key, i) {
+            hash_pair(*sibling, node)
+        } else {
+            hash_pair(node, *sibling)
+        };
+    }
+
+    node == expected_root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `sample_proof` below builds its expected root with the very
+    // `tree_key`/`leaf_hash`/`hash_pair`/`bit_at` functions under test, so it
+    // only proves the verifier is internally consistent with itself, not
+    // that it agrees with zkSync Era's actual `zksync_merkle_tree` crate.
+    // `verifies_against_an_independently_hashed_root` below recomputes a
+    // small root by hand, inlining the raw Blake2s calls instead of calling
+    // back into this module, as an independent check of the hashing and bit
+    // order. Neither of these is a substitute for a fixture pulled from a
+    // real `zks_getProof`/batch `root_hash` pair against mainnet, which this
+    // sandbox has no network access to capture; that vector still needs to
+    // be added (and this module re-checked against it) before
+    // `with_proof_verification(true)` should be trusted in production.
+
+    /// Builds a minimal single-leaf proof (four deterministic filler
+    /// siblings) together with the root this module's own hashing recomputes
+    /// for it; the tests below check that the verifier accepts it and rejects
+    /// tampered variants.
+    fn sample_proof() -> (Address, StorageProof, H256) {
+        let address = Address::repeat_byte(0x11);
+        let key = H256::repeat_byte(0x22);
+        let value = H256::repeat_byte(0x33);
+        let index = 7u64;
+        let siblings: Vec<H256> = (0..4u8).map(H256::repeat_byte).collect();
+
+        let tk = tree_key(address, key);
+        let mut node = leaf_hash(tk, value, index);
+        for (i, sibling) in siblings.iter().enumerate() {
+            node = if bit_at(&tk, i) {
+                hash_pair(*sibling, node)
+            } else {
+                hash_pair(node, *sibling)
+            };
+        }
+
+        let proof = StorageProof {
+            key,
+            proof: siblings,
+            index,
+            value,
+        };
+        (address, proof, node)
+    }
+
+    #[test]
+    fn verifies_a_correctly_constructed_proof() {
+        let (address, proof, root) = sample_proof();
+        assert!(verify_storage_proof(address, &proof, root));
+    }
+
+    #[test]
+    fn rejects_a_tampered_value() {
+        let (address, mut proof, root) = sample_proof();
+        proof.value = H256::repeat_byte(0x44);
+        assert!(!verify_storage_proof(address, &proof, root));
+    }
+
+    #[test]
+    fn rejects_a_proof_verified_against_the_wrong_address() {
+        let (_, proof, root) = sample_proof();
+        let other_address = Address::repeat_byte(0x55);
+        assert!(!verify_storage_proof(other_address, &proof, root));
+    }
+
+    /// Unlike `sample_proof`, this pins down `bit_at`'s convention with
+    /// literal, hand-checkable bytes rather than round-tripping through the
+    /// rest of the module, so a future change to the bit order trips this
+    /// test even if it's internally consistent with `verify_storage_proof`.
+    #[test]
+    fn bit_at_reads_the_least_significant_bit_first() {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 0b0000_0001; // the 256-bit key's least significant bit
+        let key = H256::from_slice(&bytes);
+        assert!(bit_at(&key, 0));
+        assert!(!bit_at(&key, 1));
+
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0b1000_0000; // the 256-bit key's most significant bit
+        let key = H256::from_slice(&bytes);
+        assert!(bit_at(&key, 255));
+        assert!(!bit_at(&key, 254));
+    }
+
+    /// Recomputes the same root as `sample_proof`, but by inlining the raw
+    /// Blake2s concatenations here instead of calling `hash_pair`/
+    /// `leaf_hash`, so this doesn't just check the module against itself.
+    #[test]
+    fn verifies_against_an_independently_hashed_root() {
+        let address = Address::repeat_byte(0x11);
+        let key = H256::repeat_byte(0x22);
+        let value = H256::repeat_byte(0x33);
+        let index = 7u64;
+        let siblings: Vec<H256> = (0..4u8).map(H256::repeat_byte).collect();
+
+        let mut hasher = Blake2s256::new();
+        hasher.update(address.as_bytes());
+        hasher.update(key.as_bytes());
+        let tk = H256::from_slice(&hasher.finalize());
+
+        let mut hasher = Blake2s256::new();
+        hasher.update(tk.as_bytes());
+        hasher.update(value.as_bytes());
+        hasher.update(index.to_be_bytes());
+        let mut node = H256::from_slice(&hasher.finalize());
+
+        for (i, sibling) in siblings.iter().enumerate() {
+            let bit = {
+                let byte = tk.as_bytes()[31 - i / 8];
+                (byte >> (i % 8)) & 1 == 1
+            };
+            let mut hasher = Blake2s256::new();
+            if bit {
+                hasher.update(sibling.as_bytes());
+                hasher.update(node.as_bytes());
+            } else {
+                hasher.update(node.as_bytes());
+                hasher.update(sibling.as_bytes());
+            }
+            node = H256::from_slice(&hasher.finalize());
+        }
+
+        let proof = StorageProof {
+            key,
+            proof: siblings,
+            index,
+            value,
+        };
+        assert!(verify_storage_proof(address, &proof, node));
+    }
+}
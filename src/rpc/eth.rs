@@ -0,0 +1,122 @@
+use jsonrpsee::{core::async_trait, proc_macros::rpc, Extensions};
+use serde_json::json;
+use zksync_types::{
+    api::{Block, BlockIdVariant, BlockNumber, TransactionVariant},
+    transaction_request::CallRequest,
+    web3::Bytes,
+    Address, H256, U256, U64,
+};
+
+use crate::{rpc::Server, ClaraError};
+
+/// Standard Ethereum JSON-RPC methods, forwarded to the upstream zkSync node
+/// so generic wallets (MetaMask and friends) can point at Clara directly.
+#[rpc(server, client, namespace = "eth")]
+pub trait EthApi {
+    #[method(name = "chainId")]
+    async fn chain_id(&self, ext: &Extensions) -> Result<U64, ClaraError>;
+
+    #[method(name = "blockNumber")]
+    async fn block_number(&self, ext: &Extensions) -> Result<U64, ClaraError>;
+
+    #[method(name = "getBlockByNumber")]
+    async fn get_block_by_number(
+        &self,
+        block_number: BlockNumber,
+        full_transactions: bool,
+        ext: &Extensions,
+    ) -> Result<Option<Block<TransactionVariant>>, ClaraError>;
+
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        address: Address,
+        block: Option<BlockIdVariant>,
+        ext: &Extensions,
+    ) -> Result<U256, ClaraError>;
+
+    #[method(name = "call")]
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        ext: &Extensions,
+    ) -> Result<Bytes, ClaraError>;
+
+    #[method(name = "sendRawTransaction")]
+    async fn send_raw_transaction(&self, tx_bytes: Bytes, ext: &Extensions) -> Result<H256, ClaraError>;
+}
+
+#[async_trait]
+impl EthApiServer for Server {
+    async fn chain_id(&self, ext: &Extensions) -> Result<U64, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("eth_chainId", json!([]), peer, async {
+            self.zksync.chain_id().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn block_number(&self, ext: &Extensions) -> Result<U64, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("eth_blockNumber", json!([]), peer, async {
+            self.zksync.block_number().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_block_by_number(
+        &self,
+        block_number: BlockNumber,
+        full_transactions: bool,
+        ext: &Extensions,
+    ) -> Result<Option<Block<TransactionVariant>>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward(
+            "eth_getBlockByNumber",
+            json!([block_number, full_transactions]),
+            peer,
+            async {
+                self.zksync
+                    .get_block_by_number(block_number, full_transactions)
+                    .await
+                    .map_err(Into::into)
+            },
+        )
+        .await
+    }
+
+    async fn get_balance(
+        &self,
+        address: Address,
+        block: Option<BlockIdVariant>,
+        ext: &Extensions,
+    ) -> Result<U256, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("eth_getBalance", json!([address, &block]), peer, async {
+            self.zksync.get_balance(address, block).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        ext: &Extensions,
+    ) -> Result<Bytes, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("eth_call", json!([&req, &block]), peer, async {
+            self.zksync.call(req, block).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn send_raw_transaction(&self, tx_bytes: Bytes, ext: &Extensions) -> Result<H256, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("eth_sendRawTransaction", json!([&tx_bytes]), peer, async {
+            self.zksync.send_raw_transaction(tx_bytes).await.map_err(Into::into)
+        })
+        .await
+    }
+}
@@ -0,0 +1,217 @@
+mod eth;
+mod net;
+mod pubsub;
+mod web3;
+mod zks;
+
+pub use eth::{EthApiClient, EthApiServer};
+pub use net::{NetApiClient, NetApiServer};
+pub use pubsub::{EthPubSubClient, EthPubSubServer};
+pub use web3::{Web3ApiClient, Web3ApiServer};
+pub use zks::{ZksApiClient, ZksApiServer};
+
+use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
+
+use jsonrpsee::{
+    http_client::HttpClient,
+    server::{ServerBuilder, ServerHandle},
+    Extensions,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    cache::ResponseCache,
+    fees::EscalationPolicy,
+    middleware::Middleware,
+    pool::{SelectionPolicy, UpstreamPool},
+    ClaraError,
+};
+
+#[derive(Clone, Debug)]
+pub struct Server {
+    pub zksync: UpstreamPool,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    cache: Option<Arc<ResponseCache>>,
+    addr: String,
+    poll_interval: Duration,
+    pub(crate) verify_proofs: bool,
+    escalation_policy: Arc<EscalationPolicy>,
+    pubsub: pubsub::PubSub,
+}
+
+impl Server {
+    /// `upstreams` is tried in `policy` order on every call, falling back to
+    /// the next endpoint on a transport-level `ClientError` rather than
+    /// surfacing it to the caller immediately; other errors (a reverted call,
+    /// invalid params) are returned without trying another endpoint.
+    pub fn new(upstreams: Vec<HttpClient>, policy: SelectionPolicy) -> Server {
+        Server {
+            zksync: UpstreamPool::new(upstreams, policy),
+            middlewares: Vec::new(),
+            cache: None,
+            addr: "127.0.0.1:7000".to_string(),
+            poll_interval: pubsub::default_poll_interval(),
+            verify_proofs: false,
+            escalation_policy: Arc::new(EscalationPolicy::default()),
+            pubsub: pubsub::PubSub::default(),
+        }
+    }
+
+    /// Registers a middleware to run, in order, ahead of every proxied call.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Overrides the default bind address (`127.0.0.1:7000`).
+    pub fn with_addr(mut self, addr: impl Into<String>) -> Self {
+        self.addr = addr.into();
+        self
+    }
+
+    /// Overrides how often subscription background tasks poll the upstream
+    /// node for new blocks (default: 2 seconds).
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// When enabled, `zks_getProof` results are verified locally against the
+    /// batch's state root before being returned, instead of being forwarded
+    /// unverified. Disabled by default.
+    pub fn with_proof_verification(mut self, verify: bool) -> Self {
+        self.verify_proofs = verify;
+        self
+    }
+
+    /// Enables response caching with the given policy. Disabled by default.
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Hit/miss counters for the response cache, if enabled.
+    pub fn cache_metrics(&self) -> Option<&crate::cache::CacheMetrics> {
+        self.cache.as_ref().map(|cache| cache.metrics())
+    }
+
+    /// Swaps in a custom fee escalation policy (default: +10%/attempt, linear).
+    pub fn with_escalation_policy(mut self, policy: EscalationPolicy) -> Self {
+        self.escalation_policy = Arc::new(policy);
+        self
+    }
+
+    /// Bumps `original_fee` for resubmission attempt number `attempt`
+    /// according to the configured escalation policy.
+    pub fn escalate_fee(&self, original_fee: zksync_types::U256, attempt: usize) -> zksync_types::U256 {
+        self.escalation_policy.escalate(original_fee, attempt)
+    }
+
+    /// Runs `method`/`params` through the full middleware + cache pipeline:
+    /// `before_call` hooks first (any `Err` short-circuits before `fetch`
+    /// ever runs), then a cache lookup, then `fetch` on a miss (with the
+    /// result cached when `cacheable` says so), then `after_call` hooks
+    /// with the outcome. A cache hit is returned without consulting
+    /// `cacheable`, which only decides whether a fetched result is stored; use
+    /// [`Server::forward`] when every successful result should be cached.
+    ///
+    /// `peer` is the caller's address, when one is available (see
+    /// [`peer_addr`]), so that per-caller middleware like rate limiting can
+    /// key on it instead of limiting every caller as one.
+    pub(crate) async fn forward_if<T, Fut>(
+        &self,
+        method: &str,
+        params: Value,
+        peer: Option<SocketAddr>,
+        fetch: Fut,
+        cacheable: impl FnOnce(&T) -> bool,
+    ) -> Result<T, ClaraError>
+    where
+        T: Serialize + DeserializeOwned,
+        Fut: Future<Output = Result<T, ClaraError>>,
+    {
+        for middleware in &self.middlewares {
+            middleware.before_call(method, &params, peer).await?;
+        }
+
+        let result = self.cached_fetch(method, &params, fetch, cacheable).await;
+
+        let logged = match &result {
+            Ok(value) => Ok(serde_json::to_value(value).unwrap_or(Value::Null)),
+            Err(error) => Err(error.to_string()),
+        };
+        for middleware in &self.middlewares {
+            middleware.after_call(method, &params, peer, &logged).await;
+        }
+
+        result
+    }
+
+    /// Like [`Server::forward_if`], but every successful result is cacheable.
+    pub(crate) async fn forward<T, Fut>(
+        &self,
+        method: &str,
+        params: Value,
+        peer: Option<SocketAddr>,
+        fetch: Fut,
+    ) -> Result<T, ClaraError>
+    where
+        T: Serialize + DeserializeOwned,
+        Fut: Future<Output = Result<T, ClaraError>>,
+    {
+        self.forward_if(method, params, peer, fetch, |_| true).await
+    }
+
+    async fn cached_fetch<T, Fut>(
+        &self,
+        method: &str,
+        params: &Value,
+        fetch: Fut,
+        cacheable: impl FnOnce(&T) -> bool,
+    ) -> Result<T, ClaraError>
+    where
+        T: Serialize + DeserializeOwned,
+        Fut: Future<Output = Result<T, ClaraError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return fetch.await;
+        };
+
+        if let Some(cached) = cache.get(method, params) {
+            return serde_json::from_value(cached).map_err(|e| anyhow::Error::from(e).into());
+        }
+
+        let value = fetch.await?;
+        if cacheable(&value) {
+            if let Ok(serialized) = serde_json::to_value(&value) {
+                cache.insert(method, params, serialized);
+            }
+        }
+        Ok(value)
+    }
+
+    pub async fn run(self) -> anyhow::Result<(SocketAddr, ServerHandle)> {
+        let server = ServerBuilder::default().build(&self.addr).await.unwrap();
+
+        let mut rpc = ZksApiServer::into_rpc(self.clone());
+        rpc.merge(EthApiServer::into_rpc(self.clone()))?;
+        rpc.merge(NetApiServer::into_rpc(self.clone()))?;
+        rpc.merge(Web3ApiServer::into_rpc(self.clone()))?;
+        rpc.merge(EthPubSubServer::into_rpc(self))?;
+
+        let addr = server.local_addr()?;
+        let handle = server.start(rpc);
+        Ok((addr, handle))
+    }
+}
+
+/// Reads the caller's remote address out of a method call's connection
+/// `Extensions`, where the transport exposed one. `jsonrpsee` populates this
+/// per call for connection-oriented transports (TCP, WS); it's absent for
+/// transports that don't carry a peer address (e.g. in-process testing), in
+/// which case callers fall back to a shared bucket (see `middleware`'s
+/// `RateLimitMiddleware`).
+pub(crate) fn peer_addr(ext: &Extensions) -> Option<SocketAddr> {
+    ext.get::<SocketAddr>().copied()
+}
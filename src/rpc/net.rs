@@ -0,0 +1,23 @@
+use jsonrpsee::{core::async_trait, proc_macros::rpc, Extensions};
+use serde_json::json;
+
+use crate::{rpc::Server, ClaraError};
+
+/// `net_*` shim so wallets that probe the network namespace before talking
+/// `eth_*` still see a well-formed response.
+#[rpc(server, client, namespace = "net")]
+pub trait NetApi {
+    #[method(name = "version")]
+    async fn version(&self, ext: &Extensions) -> Result<String, ClaraError>;
+}
+
+#[async_trait]
+impl NetApiServer for Server {
+    async fn version(&self, ext: &Extensions) -> Result<String, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("net_version", json!([]), peer, async {
+            self.zksync.version().await.map_err(Into::into)
+        })
+        .await
+    }
+}
@@ -0,0 +1,22 @@
+use jsonrpsee::{core::async_trait, proc_macros::rpc, Extensions};
+use serde_json::json;
+
+use crate::{rpc::Server, ClaraError};
+
+/// `web3_*` shim, the other namespace wallets probe before settling on `eth_*`.
+#[rpc(server, client, namespace = "web3")]
+pub trait Web3Api {
+    #[method(name = "clientVersion")]
+    async fn client_version(&self, ext: &Extensions) -> Result<String, ClaraError>;
+}
+
+#[async_trait]
+impl Web3ApiServer for Server {
+    async fn client_version(&self, ext: &Extensions) -> Result<String, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("web3_clientVersion", json!([]), peer, async {
+            self.zksync.client_version().await.map_err(Into::into)
+        })
+        .await
+    }
+}
@@ -0,0 +1,483 @@
+use std::collections::HashMap;
+
+use bigdecimal::BigDecimal;
+use jsonrpsee::{core::async_trait, proc_macros::rpc, Extensions};
+use serde_json::json;
+use zksync_types::{
+    api::{
+        BlockDetails, BlockNumber, BlockStatus, BridgeAddresses, L1BatchDetails, L2ToL1LogProof,
+        Proof, ProtocolVersion, TransactionDetails,
+    },
+    fee_model::FeeParams,
+    transaction_request::CallRequest,
+    Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
+};
+use zksync_web3_decl::types::Token;
+
+use crate::{
+    fees::{percentile, FeeSuggestion},
+    rpc::{EthApiClient, Server},
+    ClaraError,
+};
+
+#[rpc(server, client, namespace = "zks")]
+pub trait ZksApi {
+    #[method(name = "estimateGasL1ToL2")]
+    async fn estimate_gas_l1_to_l2(&self, req: CallRequest, ext: &Extensions) -> Result<U256, ClaraError>;
+
+    #[method(name = "getMainContract")]
+    async fn get_main_contract(&self, ext: &Extensions) -> Result<Address, ClaraError>;
+
+    #[method(name = "getTestnetPaymaster")]
+    async fn get_testnet_paymaster(&self, ext: &Extensions) -> Result<Option<Address>, ClaraError>;
+
+    #[method(name = "getBridgeContracts")]
+    async fn get_bridge_contracts(&self, ext: &Extensions) -> Result<BridgeAddresses, ClaraError>;
+
+    #[method(name = "L1ChainId")]
+    async fn l1_chain_id(&self, ext: &Extensions) -> Result<U64, ClaraError>;
+
+    #[method(name = "getConfirmedTokens")]
+    async fn get_confirmed_tokens(&self, from: u32, limit: u8, ext: &Extensions) -> Result<Vec<Token>, ClaraError>;
+
+    #[method(name = "getTokenPrice")]
+    async fn get_token_price(&self, token_address: Address, ext: &Extensions) -> Result<BigDecimal, ClaraError>;
+
+    #[method(name = "getAllAccountBalances")]
+    async fn get_all_account_balances(
+        &self,
+        address: Address,
+        ext: &Extensions,
+    ) -> Result<HashMap<Address, U256>, ClaraError>;
+
+    #[method(name = "getL2ToL1MsgProof")]
+    async fn get_l2_to_l1_msg_proof(
+        &self,
+        block: MiniblockNumber,
+        sender: Address,
+        msg: H256,
+        l2_log_position: Option<usize>,
+        ext: &Extensions,
+    ) -> Result<Option<L2ToL1LogProof>, ClaraError>;
+
+    #[method(name = "getL2ToL1LogProof")]
+    async fn get_l2_to_l1_log_proof(
+        &self,
+        tx_hash: H256,
+        index: Option<usize>,
+        ext: &Extensions,
+    ) -> Result<Option<L2ToL1LogProof>, ClaraError>;
+
+    #[method(name = "L1BatchNumber")]
+    async fn get_l1_batch_number(&self, ext: &Extensions) -> Result<U64, ClaraError>;
+
+    #[method(name = "getL1BatchBlockRange")]
+    async fn get_miniblock_range(
+        &self,
+        batch: L1BatchNumber,
+        ext: &Extensions,
+    ) -> Result<Option<(U64, U64)>, ClaraError>;
+
+    #[method(name = "getBlockDetails")]
+    async fn get_block_details(
+        &self,
+        block_number: MiniblockNumber,
+        ext: &Extensions,
+    ) -> Result<Option<BlockDetails>, ClaraError>;
+
+    #[method(name = "getTransactionDetails")]
+    async fn get_transaction_details(
+        &self,
+        hash: H256,
+        ext: &Extensions,
+    ) -> Result<Option<TransactionDetails>, ClaraError>;
+
+    #[method(name = "getRawBlockTransactions")]
+    async fn get_raw_block_transactions(
+        &self,
+        block_number: MiniblockNumber,
+        ext: &Extensions,
+    ) -> Result<Vec<zksync_types::Transaction>, ClaraError>;
+
+    #[method(name = "getL1BatchDetails")]
+    async fn get_l1_batch_details(
+        &self,
+        batch: L1BatchNumber,
+        ext: &Extensions,
+    ) -> Result<Option<L1BatchDetails>, ClaraError>;
+
+    #[method(name = "getBytecodeByHash")]
+    async fn get_bytecode_by_hash(&self, hash: H256, ext: &Extensions) -> Result<Option<Vec<u8>>, ClaraError>;
+
+    #[method(name = "getL1GasPrice")]
+    async fn get_l1_gas_price(&self, ext: &Extensions) -> Result<U64, ClaraError>;
+
+    #[method(name = "getFeeParams")]
+    async fn get_fee_params(&self, ext: &Extensions) -> Result<FeeParams, ClaraError>;
+
+    #[method(name = "getProtocolVersion")]
+    async fn get_protocol_version(
+        &self,
+        version_id: Option<u16>,
+        ext: &Extensions,
+    ) -> Result<Option<ProtocolVersion>, ClaraError>;
+
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        l1_batch_number: L1BatchNumber,
+        ext: &Extensions,
+    ) -> Result<Proof, ClaraError>;
+
+    #[method(name = "suggestFees")]
+    async fn suggest_fees(&self, sample_blocks: u8, ext: &Extensions) -> Result<FeeSuggestion, ClaraError>;
+}
+
+#[async_trait]
+impl ZksApiServer for Server {
+    async fn estimate_gas_l1_to_l2(&self, req: CallRequest, ext: &Extensions) -> Result<U256, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_estimateGasL1ToL2", json!([&req]), peer, async {
+            self.zksync.estimate_gas_l1_to_l2(req).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_main_contract(&self, ext: &Extensions) -> Result<Address, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getMainContract", json!([]), peer, async {
+            self.zksync.get_main_contract().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_testnet_paymaster(&self, ext: &Extensions) -> Result<Option<Address>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getTestnetPaymaster", json!([]), peer, async {
+            self.zksync.get_testnet_paymaster().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_bridge_contracts(&self, ext: &Extensions) -> Result<BridgeAddresses, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getBridgeContracts", json!([]), peer, async {
+            self.zksync.get_bridge_contracts().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn l1_chain_id(&self, ext: &Extensions) -> Result<U64, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_L1ChainId", json!([]), peer, async {
+            self.zksync.l1_chain_id().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_confirmed_tokens(&self, from: u32, limit: u8, ext: &Extensions) -> Result<Vec<Token>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getConfirmedTokens", json!([from, limit]), peer, async {
+            self.zksync.get_confirmed_tokens(from, limit).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_token_price(&self, token_address: Address, ext: &Extensions) -> Result<BigDecimal, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getTokenPrice", json!([token_address]), peer, async {
+            self.zksync.get_token_price(token_address).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_all_account_balances(
+        &self,
+        address: Address,
+        ext: &Extensions,
+    ) -> Result<HashMap<Address, U256>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getAllAccountBalances", json!([address]), peer, async {
+            self.zksync
+                .get_all_account_balances(address)
+                .await
+                .map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_l2_to_l1_msg_proof(
+        &self,
+        block: MiniblockNumber,
+        sender: Address,
+        msg: H256,
+        l2_log_position: Option<usize>,
+        ext: &Extensions,
+    ) -> Result<Option<L2ToL1LogProof>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward(
+            "zks_getL2ToL1MsgProof",
+            json!([block, sender, msg, l2_log_position]),
+            peer,
+            async {
+                self.zksync
+                    .get_l2_to_l1_msg_proof(block, sender, msg, l2_log_position)
+                    .await
+                    .map_err(Into::into)
+            },
+        )
+        .await
+    }
+
+    async fn get_l2_to_l1_log_proof(
+        &self,
+        tx_hash: H256,
+        index: Option<usize>,
+        ext: &Extensions,
+    ) -> Result<Option<L2ToL1LogProof>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getL2ToL1LogProof", json!([tx_hash, index]), peer, async {
+            self.zksync
+                .get_l2_to_l1_log_proof(tx_hash, index)
+                .await
+                .map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_l1_batch_number(&self, ext: &Extensions) -> Result<U64, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_L1BatchNumber", json!([]), peer, async {
+            self.zksync.get_l1_batch_number().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_miniblock_range(
+        &self,
+        batch: L1BatchNumber,
+        ext: &Extensions,
+    ) -> Result<Option<(U64, U64)>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getL1BatchBlockRange", json!([batch]), peer, async {
+            self.zksync.get_miniblock_range(batch).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_block_details(
+        &self,
+        block_number: MiniblockNumber,
+        ext: &Extensions,
+    ) -> Result<Option<BlockDetails>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward_if(
+            "zks_getBlockDetails",
+            json!([block_number]),
+            peer,
+            async { self.zksync.get_block_details(block_number).await.map_err(Into::into) },
+            is_block_proven,
+        )
+        .await
+    }
+
+    async fn get_transaction_details(
+        &self,
+        hash: H256,
+        ext: &Extensions,
+    ) -> Result<Option<TransactionDetails>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getTransactionDetails", json!([hash]), peer, async {
+            self.zksync.get_transaction_details(hash).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_raw_block_transactions(
+        &self,
+        block_number: MiniblockNumber,
+        ext: &Extensions,
+    ) -> Result<Vec<zksync_types::Transaction>, ClaraError> {
+        let peer = super::peer_addr(ext);
+
+        // Transactions carry no proven/sealed status of their own, so the
+        // block they belong to has to be consulted before this result is
+        // safe to cache indefinitely. Only pay for that extra lookup when
+        // there's a cache to actually benefit from it, and route it through
+        // `get_block_details` (rather than hitting the pool directly) so it
+        // shares that cache instead of doubling upstream round-trips on
+        // every call.
+        let proven = if self.cache.is_some() {
+            matches!(
+                self.get_block_details(block_number, ext).await,
+                Ok(details) if is_block_proven(&details)
+            )
+        } else {
+            false
+        };
+
+        self.forward_if(
+            "zks_getRawBlockTransactions",
+            json!([block_number]),
+            peer,
+            async {
+                self.zksync
+                    .get_raw_block_transactions(block_number)
+                    .await
+                    .map_err(Into::into)
+            },
+            |_| proven,
+        )
+        .await
+    }
+
+    async fn get_l1_batch_details(
+        &self,
+        batch: L1BatchNumber,
+        ext: &Extensions,
+    ) -> Result<Option<L1BatchDetails>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward_if(
+            "zks_getL1BatchDetails",
+            json!([batch]),
+            peer,
+            async { self.zksync.get_l1_batch_details(batch).await.map_err(Into::into) },
+            is_batch_proven,
+        )
+        .await
+    }
+
+    async fn get_bytecode_by_hash(&self, hash: H256, ext: &Extensions) -> Result<Option<Vec<u8>>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getBytecodeByHash", json!([hash]), peer, async {
+            self.zksync.get_bytecode_by_hash(hash).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_l1_gas_price(&self, ext: &Extensions) -> Result<U64, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getL1GasPrice", json!([]), peer, async {
+            self.zksync.get_l1_gas_price().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_fee_params(&self, ext: &Extensions) -> Result<FeeParams, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getFeeParams", json!([]), peer, async {
+            self.zksync.get_fee_params().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_protocol_version(
+        &self,
+        version_id: Option<u16>,
+        ext: &Extensions,
+    ) -> Result<Option<ProtocolVersion>, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_getProtocolVersion", json!([version_id]), peer, async {
+            self.zksync.get_protocol_version(version_id).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        l1_batch_number: L1BatchNumber,
+        ext: &Extensions,
+    ) -> Result<Proof, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward(
+            "zks_getProof",
+            json!([address, &keys, l1_batch_number]),
+            peer,
+            async {
+                let proof = self.zksync.get_proof(address, keys, l1_batch_number).await?;
+
+                if self.verify_proofs {
+                    let details = self
+                        .zksync
+                        .get_l1_batch_details(l1_batch_number)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("l1 batch {l1_batch_number} has no details yet"))?;
+                    let root_hash = details.base.root_hash.ok_or(ClaraError::BatchRootUnavailable {
+                        batch: l1_batch_number,
+                    })?;
+
+                    for storage_proof in &proof.storage_proof {
+                        if !crate::proof::verify_storage_proof(address, storage_proof, root_hash) {
+                            return Err(ClaraError::ProofVerificationFailed {
+                                address,
+                                key: storage_proof.key,
+                            });
+                        }
+                    }
+                }
+
+                Ok(proof)
+            },
+        )
+        .await
+    }
+
+    async fn suggest_fees(&self, sample_blocks: u8, ext: &Extensions) -> Result<FeeSuggestion, ClaraError> {
+        let peer = super::peer_addr(ext);
+        self.forward("zks_suggestFees", json!([sample_blocks]), peer, async {
+            let latest = self.zksync.block_number().await?.as_u64();
+            let sample_blocks = u64::from(sample_blocks.max(1));
+            let from = latest.saturating_sub(sample_blocks - 1);
+
+            let mut base_fees = Vec::new();
+            let mut gas_used_ratios = Vec::new();
+            for number in from..=latest {
+                if let Some(block) = self
+                    .zksync
+                    .get_block_by_number(BlockNumber::Number(U64::from(number)), false)
+                    .await?
+                {
+                    base_fees.push(block.base_fee_per_gas);
+                    if !block.gas_limit.is_zero() {
+                        gas_used_ratios.push(
+                            block.gas_used.as_u128() as f64 / block.gas_limit.as_u128() as f64,
+                        );
+                    }
+                }
+            }
+            base_fees.sort();
+
+            let median = percentile(&base_fees, 50).unwrap_or_default();
+            let p90 = percentile(&base_fees, 90).unwrap_or(median);
+            let priority_fee = p90.saturating_sub(median) / U256::from(10);
+            let gas_used_ratio = if gas_used_ratios.is_empty() {
+                0.0
+            } else {
+                gas_used_ratios.iter().sum::<f64>() / gas_used_ratios.len() as f64
+            };
+
+            Ok(FeeSuggestion {
+                max_fee_per_gas: median.saturating_mul(U256::from(2)) + priority_fee,
+                max_priority_fee_per_gas: priority_fee,
+                gas_used_ratio,
+            })
+        })
+        .await
+    }
+}
+
+/// A block's details are safe to cache indefinitely only once its batch has
+/// been verified on L1 — before that it (and its root hash) can still be
+/// reorganized.
+fn is_block_proven(details: &Option<BlockDetails>) -> bool {
+    matches!(details, Some(details) if details.base.status == BlockStatus::Verified)
+}
+
+/// Same reasoning as [`is_block_proven`], for L1 batch details.
+fn is_batch_proven(details: &Option<L1BatchDetails>) -> bool {
+    matches!(details, Some(details) if details.base.status == BlockStatus::Verified)
+}